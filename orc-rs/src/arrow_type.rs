@@ -0,0 +1,420 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Bidirectional conversion between ORC [`DataType`]/[`Field`] and
+//! `arrow::datatypes::{DataType, Field, Schema}`, so an ORC schema can be
+//! handed directly to Arrow-based engines and vice versa.
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use arrow::datatypes::{
+    DataType as ArrowDataType, Field as ArrowField, FieldRef, Fields as ArrowFields, Schema,
+    TimeUnit, UnionFields, UnionMode,
+};
+
+use crate::data_type::{create_char_type, create_decimal_type, DataType, Field, ThinType, TypeKind};
+use crate::error::{OrcError, OrcResult};
+
+/// Arrow field metadata key that records the declared length of an ORC
+/// `Char`/`Varchar` column, since Arrow has no bounded-length UTF-8 type.
+pub const ORC_MAX_LENGTH_METADATA_KEY: &str = "orc.max_length";
+
+/// Arrow field metadata key that records whether an ORC `Char`/`Varchar`
+/// column was fixed-width (`"CHAR"`) or variable-width (`"VARCHAR"`), so the
+/// distinction survives an ORC -> Arrow -> ORC round trip.
+pub const ORC_CHAR_KIND_METADATA_KEY: &str = "orc.char_kind";
+
+impl TryFrom<&ArrowDataType> for Box<DataType> {
+    type Error = OrcError;
+
+    fn try_from(value: &ArrowDataType) -> OrcResult<Self> {
+        arrow_data_type_to_orc(value, None)
+    }
+}
+
+impl TryFrom<&ArrowField> for Box<Field> {
+    type Error = OrcError;
+
+    fn try_from(value: &ArrowField) -> OrcResult<Self> {
+        let datatype = arrow_data_type_to_orc(value.data_type(), Some(value))?;
+        Ok(Box::new(Field {
+            name: value.name().clone(),
+            datatype,
+        }))
+    }
+}
+
+impl TryFrom<&Schema> for Box<DataType> {
+    type Error = OrcError;
+
+    fn try_from(value: &Schema) -> OrcResult<Self> {
+        let fields = value
+            .fields()
+            .iter()
+            .map(|f| Box::<Field>::try_from(f.as_ref()))
+            .collect::<OrcResult<Vec<_>>>()?;
+        Ok(Box::new(DataType::new(&ThinType::Struct(fields))))
+    }
+}
+
+impl TryFrom<&DataType> for ArrowDataType {
+    type Error = OrcError;
+
+    fn try_from(value: &DataType) -> OrcResult<Self> {
+        orc_data_type_to_arrow(value)
+    }
+}
+
+impl TryFrom<&Field> for ArrowField {
+    type Error = OrcError;
+
+    fn try_from(value: &Field) -> OrcResult<Self> {
+        orc_to_arrow_field(&value.name, &value.datatype, true)
+    }
+}
+
+/// Builds a single Arrow field from an ORC (sub)type, attaching the
+/// `orc.max_length`/`orc.char_kind` metadata for `Char`/`Varchar` so the
+/// distinction survives an ORC -> Arrow -> ORC round trip. Used both for
+/// named `Struct` fields and for the synthetic fields Arrow requires for
+/// `List`/`Map`/`Union` elements.
+fn orc_to_arrow_field(
+    name: impl Into<String>,
+    datatype: &DataType,
+    nullable: bool,
+) -> OrcResult<ArrowField> {
+    let mut field = ArrowField::new(name, ArrowDataType::try_from(datatype)?, nullable);
+    match &datatype.thin_type {
+        ThinType::Char(max_length) => {
+            field.set_metadata(
+                [
+                    (ORC_MAX_LENGTH_METADATA_KEY.to_string(), max_length.to_string()),
+                    (ORC_CHAR_KIND_METADATA_KEY.to_string(), "CHAR".to_string()),
+                ]
+                .into_iter()
+                .collect(),
+            );
+        }
+        ThinType::Varchar(max_length) => {
+            field.set_metadata(
+                [
+                    (ORC_MAX_LENGTH_METADATA_KEY.to_string(), max_length.to_string()),
+                    (ORC_CHAR_KIND_METADATA_KEY.to_string(), "VARCHAR".to_string()),
+                ]
+                .into_iter()
+                .collect(),
+            );
+        }
+        _ => {}
+    }
+    Ok(field)
+}
+
+impl TryFrom<&DataType> for Schema {
+    type Error = OrcError;
+
+    fn try_from(value: &DataType) -> OrcResult<Self> {
+        match &value.thin_type {
+            ThinType::Struct(fields) => {
+                let arrow_fields = fields
+                    .iter()
+                    .map(|f| ArrowField::try_from(f.as_ref()))
+                    .collect::<OrcResult<Vec<_>>>()?;
+                Ok(Schema::new(arrow_fields))
+            }
+            _ => Err(OrcError::DataTypeError(
+                "Only a Struct DataType can be converted to an Arrow Schema".to_string(),
+            )),
+        }
+    }
+}
+
+fn arrow_data_type_to_orc(
+    value: &ArrowDataType,
+    field: Option<&ArrowField>,
+) -> OrcResult<Box<DataType>> {
+    match value {
+        ArrowDataType::Boolean => create_primitive(TypeKind::Boolean),
+        ArrowDataType::Int8 => create_primitive(TypeKind::Byte),
+        ArrowDataType::Int16 => create_primitive(TypeKind::Short),
+        ArrowDataType::Int32 => create_primitive(TypeKind::Int),
+        ArrowDataType::Int64 => create_primitive(TypeKind::Long),
+        ArrowDataType::Float32 => create_primitive(TypeKind::Float),
+        ArrowDataType::Float64 => create_primitive(TypeKind::Double),
+        ArrowDataType::Binary | ArrowDataType::LargeBinary | ArrowDataType::FixedSizeBinary(_) => {
+            create_primitive(TypeKind::Binary)
+        }
+        ArrowDataType::Date32 | ArrowDataType::Date64 => create_primitive(TypeKind::Date),
+        ArrowDataType::Timestamp(_, tz) => {
+            if tz.is_some() {
+                create_primitive(TypeKind::TimestampInstant)
+            } else {
+                create_primitive(TypeKind::Timestamp)
+            }
+        }
+        ArrowDataType::Utf8 | ArrowDataType::LargeUtf8 => {
+            match field.and_then(|f| f.metadata().get(ORC_MAX_LENGTH_METADATA_KEY)) {
+                Some(max_length) => {
+                    let max_length: u64 = max_length.parse().map_err(|_| {
+                        OrcError::DataTypeError(format!(
+                            "Invalid {ORC_MAX_LENGTH_METADATA_KEY} metadata value: {max_length}"
+                        ))
+                    })?;
+                    let kind = match field.and_then(|f| f.metadata().get(ORC_CHAR_KIND_METADATA_KEY))
+                    {
+                        Some(kind) if kind == "CHAR" => TypeKind::Char,
+                        _ => TypeKind::Varchar,
+                    };
+                    create_char_type(&kind, max_length)
+                }
+                None => create_primitive(TypeKind::String),
+            }
+        }
+        ArrowDataType::Decimal128(precision, scale) | ArrowDataType::Decimal256(precision, scale) => {
+            create_decimal_type(&TypeKind::Decimal, *precision as u64, (*scale).max(0) as u64)
+        }
+        ArrowDataType::List(element) | ArrowDataType::LargeList(element) => {
+            let element_type = arrow_data_type_to_orc(element.data_type(), Some(element))?;
+            Ok(Box::new(DataType::new(&ThinType::List(element_type))))
+        }
+        ArrowDataType::Map(entries, _) => {
+            let ArrowDataType::Struct(kv_fields) = entries.data_type() else {
+                return Err(OrcError::DataTypeError(
+                    "Arrow Map entries field must be a Struct".to_string(),
+                ));
+            };
+            if kv_fields.len() != 2 {
+                return Err(OrcError::DataTypeError(
+                    "Arrow Map entries struct must have exactly a key and a value field"
+                        .to_string(),
+                ));
+            }
+            let key_type = arrow_data_type_to_orc(kv_fields[0].data_type(), Some(&kv_fields[0]))?;
+            let value_type = arrow_data_type_to_orc(kv_fields[1].data_type(), Some(&kv_fields[1]))?;
+            Ok(Box::new(DataType::new(&ThinType::Map(key_type, value_type))))
+        }
+        ArrowDataType::Struct(fields) => {
+            let fields = fields
+                .iter()
+                .map(|f| Box::<Field>::try_from(f.as_ref()))
+                .collect::<OrcResult<Vec<_>>>()?;
+            Ok(Box::new(DataType::new(&ThinType::Struct(fields))))
+        }
+        ArrowDataType::Union(union_fields, _) => {
+            let variants = union_fields
+                .iter()
+                .map(|(_, f)| arrow_data_type_to_orc(f.data_type(), Some(f)))
+                .collect::<OrcResult<Vec<_>>>()?;
+            Ok(Box::new(DataType::new(&ThinType::Union(variants))))
+        }
+        other => Err(OrcError::DataTypeError(format!(
+            "Arrow DataType {other:?} has no ORC equivalent"
+        ))),
+    }
+}
+
+fn create_primitive(kind: TypeKind) -> OrcResult<Box<DataType>> {
+    crate::data_type::create_primitive_type(&kind)
+}
+
+fn orc_data_type_to_arrow(value: &DataType) -> OrcResult<ArrowDataType> {
+    match &value.thin_type {
+        ThinType::Boolean => Ok(ArrowDataType::Boolean),
+        ThinType::Byte => Ok(ArrowDataType::Int8),
+        ThinType::Short => Ok(ArrowDataType::Int16),
+        ThinType::Int => Ok(ArrowDataType::Int32),
+        ThinType::Long => Ok(ArrowDataType::Int64),
+        ThinType::Float => Ok(ArrowDataType::Float32),
+        ThinType::Double => Ok(ArrowDataType::Float64),
+        ThinType::String | ThinType::Char(_) | ThinType::Varchar(_) => Ok(ArrowDataType::Utf8),
+        ThinType::Binary => Ok(ArrowDataType::Binary),
+        ThinType::Timestamp => Ok(ArrowDataType::Timestamp(TimeUnit::Nanosecond, None)),
+        ThinType::TimestampInstant => Ok(ArrowDataType::Timestamp(
+            TimeUnit::Nanosecond,
+            Some(Arc::from("UTC")),
+        )),
+        ThinType::Date => Ok(ArrowDataType::Date32),
+        ThinType::Decimal(precision, scale) => {
+            if *precision <= 38 {
+                Ok(ArrowDataType::Decimal128(*precision as u8, *scale as i8))
+            } else if *precision <= 76 {
+                Ok(ArrowDataType::Decimal256(*precision as u8, *scale as i8))
+            } else {
+                Err(OrcError::DataTypeError(format!(
+                    "Decimal precision {precision} exceeds the maximum Arrow precision of 76"
+                )))
+            }
+        }
+        ThinType::List(element) => {
+            let element_field = orc_to_arrow_field("item", element, true)?;
+            Ok(ArrowDataType::List(Arc::new(element_field)))
+        }
+        ThinType::Map(key, value_type) => {
+            let key_field = orc_to_arrow_field("key", key, false)?;
+            let value_field = orc_to_arrow_field("value", value_type, true)?;
+            let entries = ArrowField::new(
+                "entries",
+                ArrowDataType::Struct(ArrowFields::from(vec![key_field, value_field])),
+                false,
+            );
+            Ok(ArrowDataType::Map(Arc::new(entries), false))
+        }
+        ThinType::Struct(fields) => {
+            let arrow_fields = fields
+                .iter()
+                .map(|f| ArrowField::try_from(f.as_ref()))
+                .collect::<OrcResult<Vec<_>>>()?;
+            Ok(ArrowDataType::Struct(ArrowFields::from(arrow_fields)))
+        }
+        ThinType::Union(variants) => {
+            let type_ids: Vec<i8> = (0..variants.len() as i8).collect();
+            let fields: Vec<FieldRef> = variants
+                .iter()
+                .enumerate()
+                .map(|(i, v)| orc_to_arrow_field(format!("variant_{i}"), v, true).map(Arc::new))
+                .collect::<OrcResult<Vec<_>>>()?;
+            Ok(ArrowDataType::Union(
+                UnionFields::new(type_ids, fields),
+                UnionMode::Sparse,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::{create_list_type, create_primitive_type, create_struct_type};
+
+    #[test]
+    fn test_primitive_roundtrip() {
+        let orc_type = create_primitive_type(&TypeKind::Long).unwrap();
+        let arrow_type = ArrowDataType::try_from(orc_type.as_ref()).unwrap();
+        assert_eq!(arrow_type, ArrowDataType::Int64);
+
+        let back = Box::<DataType>::try_from(&arrow_type).unwrap();
+        assert_eq!(back.thin_type, ThinType::Long);
+    }
+
+    #[test]
+    fn test_decimal_maps_to_decimal128() {
+        let orc_type = create_decimal_type(&TypeKind::Decimal, 10, 2).unwrap();
+        let arrow_type = ArrowDataType::try_from(orc_type.as_ref()).unwrap();
+        assert_eq!(arrow_type, ArrowDataType::Decimal128(10, 2));
+    }
+
+    #[test]
+    fn test_varchar_preserves_length_in_metadata() {
+        let orc_type = create_char_type(&TypeKind::Varchar, 20).unwrap();
+        let field = Field {
+            name: "v".to_string(),
+            datatype: orc_type,
+        };
+        let arrow_field = ArrowField::try_from(&field).unwrap();
+        assert_eq!(arrow_field.data_type(), &ArrowDataType::Utf8);
+        assert_eq!(
+            arrow_field.metadata().get(ORC_MAX_LENGTH_METADATA_KEY),
+            Some(&"20".to_string())
+        );
+
+        let back = Box::<Field>::try_from(&arrow_field).unwrap();
+        assert_eq!(back.datatype.thin_type, ThinType::Varchar(20));
+    }
+
+    #[test]
+    fn test_char_roundtrip_is_not_confused_with_varchar() {
+        let orc_type = create_char_type(&TypeKind::Char, 5).unwrap();
+        let field = Field {
+            name: "c".to_string(),
+            datatype: orc_type,
+        };
+        let arrow_field = ArrowField::try_from(&field).unwrap();
+        assert_eq!(
+            arrow_field.metadata().get(ORC_CHAR_KIND_METADATA_KEY),
+            Some(&"CHAR".to_string())
+        );
+
+        let back = Box::<Field>::try_from(&arrow_field).unwrap();
+        assert_eq!(back.datatype.thin_type, ThinType::Char(5));
+    }
+
+    #[test]
+    fn test_struct_roundtrip() {
+        let inner = create_list_type(&create_primitive_type(&TypeKind::Int).unwrap()).unwrap();
+        let mut strct = create_struct_type().unwrap();
+        if let ThinType::Struct(fields) = &mut strct.thin_type {
+            fields.push(Box::new(Field {
+                name: "items".to_string(),
+                datatype: inner,
+            }));
+        }
+
+        let schema = Schema::try_from(strct.as_ref()).unwrap();
+        let back = Box::<DataType>::try_from(&schema).unwrap();
+        assert_eq!(back.thin_type, strct.thin_type);
+    }
+
+    #[test]
+    fn test_char_survives_struct_list_roundtrip() {
+        use crate::data_type::{create_char_type, create_list_type as make_list};
+
+        let inner = make_list(&create_char_type(&TypeKind::Char, 5).unwrap()).unwrap();
+        let mut strct = create_struct_type().unwrap();
+        if let ThinType::Struct(fields) = &mut strct.thin_type {
+            fields.push(Box::new(Field {
+                name: "xs".to_string(),
+                datatype: inner,
+            }));
+        }
+
+        let schema = Schema::try_from(strct.as_ref()).unwrap();
+        let back = Box::<DataType>::try_from(&schema).unwrap();
+        assert_eq!(back.thin_type, strct.thin_type);
+    }
+
+    #[test]
+    fn test_char_survives_map_roundtrip() {
+        let key = create_char_type(&TypeKind::Varchar, 8).unwrap();
+        let value = create_primitive_type(&TypeKind::Int).unwrap();
+        let map = crate::data_type::create_map_type(&key, &value).unwrap();
+
+        let arrow_type = ArrowDataType::try_from(map.as_ref()).unwrap();
+        let back = Box::<DataType>::try_from(&arrow_type).unwrap();
+        assert_eq!(back.thin_type, map.thin_type);
+    }
+
+    #[test]
+    fn test_char_survives_union_roundtrip() {
+        let mut union = crate::data_type::create_union_type().unwrap();
+        if let ThinType::Union(variants) = &mut union.thin_type {
+            variants.push(create_char_type(&TypeKind::Char, 5).unwrap());
+        }
+
+        let arrow_type = ArrowDataType::try_from(union.as_ref()).unwrap();
+        let back = Box::<DataType>::try_from(&arrow_type).unwrap();
+        assert_eq!(back.thin_type, union.thin_type);
+    }
+
+    #[test]
+    fn test_unsupported_arrow_type_errors() {
+        let result = Box::<DataType>::try_from(&ArrowDataType::Interval(
+            arrow::datatypes::IntervalUnit::YearMonth,
+        ));
+        assert!(matches!(result, Err(OrcError::DataTypeError(_))));
+    }
+}
@@ -145,7 +145,12 @@ impl TryFrom<TypeKind> for ThinType {
 /// ORC data types
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct DataType {
-    parent: Box<Option<DataType>>,
+    /// The `column_id` of this node's parent in a [`finalize`]d schema tree,
+    /// or `None` for the root (or before the tree has been finalized). This
+    /// is just the id rather than a cloned/owned parent node, since storing
+    /// the actual ancestor would duplicate the whole ancestor chain (and its
+    /// sibling subtrees) at every level of the tree.
+    pub parent_column_id: Option<usize>,
     pub column_id: Option<usize>,
     pub maximum_column_id: Option<usize>,
     pub thin_type: ThinType,
@@ -165,7 +170,7 @@ pub struct Field {
 impl DataType {
     pub fn new(thin_type: &ThinType) -> Self {
         DataType {
-            parent: Box::new(None),
+            parent_column_id: None,
             column_id: None,
             maximum_column_id: None,
             thin_type: thin_type.clone(),
@@ -250,6 +255,87 @@ impl DataType {
             )),
         }
     }
+
+    /// Recursively re-checks the `Decimal`/`Char`/`Varchar` bounds invariants
+    /// across this node and all of its descendants, so a schema assembled
+    /// field-by-field can be verified in one call before it's written.
+    pub fn validate(&self) -> OrcResult<()> {
+        validate_decimal(&self.thin_type)?;
+        validate_char(&self.thin_type)?;
+        for child in self.children() {
+            child.validate()?;
+        }
+        Ok(())
+    }
+
+    /// The direct subtypes of this node, in column-id order.
+    fn children(&self) -> Vec<&DataType> {
+        match &self.thin_type {
+            ThinType::List(element) => vec![element.as_ref()],
+            ThinType::Map(key, value) => vec![key.as_ref(), value.as_ref()],
+            ThinType::Struct(fields) => fields.iter().map(|f| f.datatype.as_ref()).collect(),
+            ThinType::Union(variants) => variants.iter().map(|v| v.as_ref()).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The direct subtypes of this node, in column-id order, as mutable
+    /// references. Empty for primitive types.
+    fn children_mut(&mut self) -> Vec<&mut DataType> {
+        match &mut self.thin_type {
+            ThinType::List(element) => vec![element.as_mut()],
+            ThinType::Map(key, value) => vec![key.as_mut(), value.as_mut()],
+            ThinType::Struct(fields) => fields.iter_mut().map(|f| f.datatype.as_mut()).collect(),
+            ThinType::Union(variants) => variants.iter_mut().map(|v| v.as_mut()).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl std::fmt::Display for DataType {
+    /// Renders the ORC canonical type string, e.g.
+    /// `struct<foo:int,bar:varchar(20),baz:array<map<string,double>>>`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.thin_type {
+            ThinType::Boolean => write!(f, "boolean"),
+            ThinType::Byte => write!(f, "tinyint"),
+            ThinType::Short => write!(f, "smallint"),
+            ThinType::Int => write!(f, "int"),
+            ThinType::Long => write!(f, "bigint"),
+            ThinType::Float => write!(f, "float"),
+            ThinType::Double => write!(f, "double"),
+            ThinType::String => write!(f, "string"),
+            ThinType::Binary => write!(f, "binary"),
+            ThinType::Timestamp => write!(f, "timestamp"),
+            ThinType::TimestampInstant => write!(f, "timestamp with local time zone"),
+            ThinType::Date => write!(f, "date"),
+            ThinType::Char(max_length) => write!(f, "char({max_length})"),
+            ThinType::Varchar(max_length) => write!(f, "varchar({max_length})"),
+            ThinType::Decimal(precision, scale) => write!(f, "decimal({precision},{scale})"),
+            ThinType::List(element) => write!(f, "array<{element}>"),
+            ThinType::Map(key, value) => write!(f, "map<{key},{value}>"),
+            ThinType::Struct(fields) => {
+                write!(f, "struct<")?;
+                for (i, field) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}:{}", field.name, field.datatype)?;
+                }
+                write!(f, ">")
+            }
+            ThinType::Union(variants) => {
+                write!(f, "uniontype<")?;
+                for (i, variant) in variants.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{variant}")?;
+                }
+                write!(f, ">")
+            }
+        }
+    }
 }
 
 /// Create numerous DataTypes
@@ -263,6 +349,9 @@ pub fn create_primitive_type(kind: &TypeKind) -> OrcResult<Box<DataType>> {
     }
 }
 
+/// ORC's maximum decimal precision.
+pub const MAX_DECIMAL_PRECISION: u64 = 38;
+
 pub fn create_char_type(kind: &TypeKind, max_length: u64) -> OrcResult<Box<DataType>> {
     let thin_type_result = match kind {
         TypeKind::Char => Ok(ThinType::Char(max_length)),
@@ -271,7 +360,9 @@ pub fn create_char_type(kind: &TypeKind, max_length: u64) -> OrcResult<Box<DataT
             "The TypeKind is not Char or Varchar".to_string(),
         )),
     };
-    Ok(Box::new(DataType::new(&thin_type_result?)))
+    let thin_type = thin_type_result?;
+    validate_char(&thin_type)?;
+    Ok(Box::new(DataType::new(&thin_type)))
 }
 
 pub fn create_decimal_type(
@@ -282,6 +373,7 @@ pub fn create_decimal_type(
     match kind {
         TypeKind::Decimal => {
             let thin_type = ThinType::Decimal(precision, scale);
+            validate_decimal(&thin_type)?;
             Ok(Box::new(DataType::new(&thin_type)))
         }
         _ => Err(OrcError::DataTypeError(
@@ -290,6 +382,33 @@ pub fn create_decimal_type(
     }
 }
 
+fn validate_decimal(thin_type: &ThinType) -> OrcResult<()> {
+    if let ThinType::Decimal(precision, scale) = thin_type {
+        if *precision < 1 || *precision > MAX_DECIMAL_PRECISION {
+            return Err(OrcError::DataTypeError(format!(
+                "decimal precision {precision} is out of range for its type: must be between 1 and {MAX_DECIMAL_PRECISION}"
+            )));
+        }
+        if *scale > *precision {
+            return Err(OrcError::DataTypeError(format!(
+                "decimal scale {scale} is out of range for its type: must not exceed precision {precision}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_char(thin_type: &ThinType) -> OrcResult<()> {
+    if let ThinType::Char(max_length) | ThinType::Varchar(max_length) = thin_type {
+        if *max_length < 1 {
+            return Err(OrcError::DataTypeError(format!(
+                "max length {max_length} is out of range for its type: must be at least 1"
+            )));
+        }
+    }
+    Ok(())
+}
+
 // Create new struct type with no fields
 pub fn create_struct_type() -> OrcResult<Box<DataType>> {
     let thin_type = ThinType::Struct(Vec::new());
@@ -315,6 +434,117 @@ pub fn create_union_type() -> OrcResult<Box<DataType>> {
     Ok(Box::new(DataType::new(&thin_type)))
 }
 
+/// Walks `root` in depth-first preorder, assigning sequential `column_id`s
+/// starting at 0, filling in `subtype_count` and `maximum_column_id` (the
+/// largest id among a node and all its descendants), and setting each
+/// child's `parent_column_id`. This is what lets a reader/writer project a
+/// schema down to a subset of columns by id (see [`project`]).
+pub fn finalize(mut root: Box<DataType>) -> Box<DataType> {
+    let mut next_id = 0usize;
+    assign_column_ids(&mut root, &mut next_id);
+    compute_maximum_column_ids(&mut root);
+    wire_parents(&mut root, None);
+    root
+}
+
+fn assign_column_ids(node: &mut DataType, next_id: &mut usize) {
+    node.column_id = Some(*next_id);
+    *next_id += 1;
+    node.subtype_count = node.children().len();
+    for child in node.children_mut() {
+        assign_column_ids(child, next_id);
+    }
+}
+
+fn compute_maximum_column_ids(node: &mut DataType) -> usize {
+    let mut maximum_column_id = node.column_id.expect("column_id must be assigned first");
+    for child in node.children_mut() {
+        maximum_column_id = maximum_column_id.max(compute_maximum_column_ids(child));
+    }
+    node.maximum_column_id = Some(maximum_column_id);
+    maximum_column_id
+}
+
+fn wire_parents(node: &mut DataType, parent_column_id: Option<usize>) {
+    node.parent_column_id = parent_column_id;
+    let own_column_id = node.column_id;
+    for child in node.children_mut() {
+        wire_parents(child, own_column_id);
+    }
+}
+
+/// Prunes a finalized schema tree down to the requested leaf/subtree
+/// `column_id`s plus their ancestors, using the id ranges assigned by
+/// [`finalize`]. This is the core operation behind reading a subset of
+/// columns from an ORC file.
+pub fn project(root: &DataType, column_ids: &[usize]) -> OrcResult<Box<DataType>> {
+    if root.column_id.is_none() || root.maximum_column_id.is_none() {
+        return Err(OrcError::DataTypeError(
+            "project requires a finalized schema; call finalize() first".to_string(),
+        ));
+    }
+    Ok(project_node(root, column_ids))
+}
+
+fn subtree_overlaps(node: &DataType, column_ids: &[usize]) -> bool {
+    let lo = node.column_id.expect("column_id must be assigned first");
+    let hi = node
+        .maximum_column_id
+        .expect("maximum_column_id must be assigned first");
+    column_ids.iter().any(|&id| id >= lo && id <= hi)
+}
+
+fn project_node(node: &DataType, column_ids: &[usize]) -> Box<DataType> {
+    // A requested node keeps its whole subtree.
+    if node.column_id.is_some_and(|id| column_ids.contains(&id)) {
+        return Box::new(node.clone());
+    }
+    let mut pruned = node.clone();
+    match &node.thin_type {
+        ThinType::Struct(fields) => {
+            let kept = fields
+                .iter()
+                .filter(|field| subtree_overlaps(&field.datatype, column_ids))
+                .map(|field| {
+                    Box::new(Field {
+                        name: field.name.clone(),
+                        datatype: project_node(&field.datatype, column_ids),
+                    })
+                })
+                .collect::<Vec<_>>();
+            pruned.subtype_count = kept.len();
+            pruned.thin_type = ThinType::Struct(kept);
+        }
+        ThinType::List(element) if subtree_overlaps(element, column_ids) => {
+            pruned.thin_type = ThinType::List(project_node(element, column_ids));
+        }
+        ThinType::Map(key, value) => {
+            let key = if subtree_overlaps(key, column_ids) {
+                project_node(key, column_ids)
+            } else {
+                key.clone()
+            };
+            let value = if subtree_overlaps(value, column_ids) {
+                project_node(value, column_ids)
+            } else {
+                value.clone()
+            };
+            pruned.thin_type = ThinType::Map(key, value);
+        }
+        ThinType::Union(variants) => {
+            let kept = variants
+                .iter()
+                .filter(|variant| subtree_overlaps(variant, column_ids))
+                .map(|variant| project_node(variant, column_ids))
+                .collect::<Vec<_>>();
+            pruned.subtype_count = kept.len();
+            pruned.thin_type = ThinType::Union(kept);
+        }
+        _ => {}
+    }
+    Box::new(pruned)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -362,4 +592,121 @@ mod tests {
         let type_kind = TypeKind::Struct;
         ThinType::try_from(type_kind).unwrap();
     }
+
+    fn build_sample_schema() -> Box<DataType> {
+        // struct<a:int,b:struct<c:double,d:string>>
+        let mut root = create_struct_type().unwrap();
+        let mut inner = create_struct_type().unwrap();
+        if let ThinType::Struct(fields) = &mut inner.thin_type {
+            fields.push(Box::new(Field {
+                name: "c".to_string(),
+                datatype: create_primitive_type(&TypeKind::Double).unwrap(),
+            }));
+            fields.push(Box::new(Field {
+                name: "d".to_string(),
+                datatype: create_primitive_type(&TypeKind::String).unwrap(),
+            }));
+        }
+        if let ThinType::Struct(fields) = &mut root.thin_type {
+            fields.push(Box::new(Field {
+                name: "a".to_string(),
+                datatype: create_primitive_type(&TypeKind::Int).unwrap(),
+            }));
+            fields.push(Box::new(Field {
+                name: "b".to_string(),
+                datatype: inner,
+            }));
+        }
+        root
+    }
+
+    #[test]
+    fn test_finalize_assigns_column_ids_preorder() {
+        let root = finalize(build_sample_schema());
+        assert_eq!(root.column_id, Some(0));
+        assert_eq!(root.maximum_column_id, Some(4));
+        assert_eq!(root.subtype_count, 2);
+
+        let ThinType::Struct(fields) = &root.thin_type else {
+            panic!("expected a struct");
+        };
+        assert_eq!(fields[0].datatype.column_id, Some(1));
+        assert_eq!(fields[1].datatype.column_id, Some(2));
+        assert_eq!(fields[1].datatype.maximum_column_id, Some(4));
+
+        let ThinType::Struct(inner_fields) = &fields[1].datatype.thin_type else {
+            panic!("expected a struct");
+        };
+        assert_eq!(inner_fields[0].datatype.column_id, Some(3));
+        assert_eq!(inner_fields[1].datatype.column_id, Some(4));
+        assert_eq!(inner_fields[0].datatype.parent_column_id, Some(2));
+    }
+
+    #[test]
+    fn test_project_keeps_only_requested_columns_and_ancestors() {
+        let root = finalize(build_sample_schema());
+        // Column 4 is `b.d`; projecting it should keep `b` but drop `b.c` and `a`.
+        let projected = project(&root, &[4]).unwrap();
+        let ThinType::Struct(fields) = &projected.thin_type else {
+            panic!("expected a struct");
+        };
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].name, "b");
+
+        let ThinType::Struct(inner_fields) = &fields[0].datatype.thin_type else {
+            panic!("expected a struct");
+        };
+        assert_eq!(inner_fields.len(), 1);
+        assert_eq!(inner_fields[0].name, "d");
+    }
+
+    #[test]
+    fn test_project_requires_finalized_schema() {
+        let root = build_sample_schema();
+        assert!(matches!(
+            project(&root, &[0]),
+            Err(OrcError::DataTypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_create_decimal_type_rejects_out_of_range_precision() {
+        assert!(matches!(
+            create_decimal_type(&TypeKind::Decimal, 0, 0),
+            Err(OrcError::DataTypeError(_))
+        ));
+        assert!(matches!(
+            create_decimal_type(&TypeKind::Decimal, 39, 0),
+            Err(OrcError::DataTypeError(_))
+        ));
+        assert!(matches!(
+            create_decimal_type(&TypeKind::Decimal, 10, 11),
+            Err(OrcError::DataTypeError(_))
+        ));
+        assert!(create_decimal_type(&TypeKind::Decimal, 10, 2).is_ok());
+    }
+
+    #[test]
+    fn test_create_char_type_rejects_zero_length() {
+        assert!(matches!(
+            create_char_type(&TypeKind::Varchar, 0),
+            Err(OrcError::DataTypeError(_))
+        ));
+        assert!(create_char_type(&TypeKind::Char, 1).is_ok());
+    }
+
+    #[test]
+    fn test_validate_catches_invariant_violations_assembled_by_hand() {
+        let mut root = create_struct_type().unwrap();
+        if let ThinType::Struct(fields) = &mut root.thin_type {
+            fields.push(Box::new(Field {
+                name: "bad_decimal".to_string(),
+                datatype: Box::new(DataType::new(&ThinType::Decimal(50, 2))),
+            }));
+        }
+        assert!(matches!(root.validate(), Err(OrcError::DataTypeError(_))));
+
+        let valid = build_sample_schema();
+        assert!(valid.validate().is_ok());
+    }
 }
@@ -1,16 +1,35 @@
-//! Contains [`Error`]
+//! Contains [`OrcError`]
+use std::fmt;
 use std::sync::Arc;
 
+/// A specialized `Result` type for operations in this crate.
+pub type OrcResult<T> = Result<T, OrcError>;
+
 /// Errors generated by this crate
 #[derive(Debug, Clone)]
 #[non_exhaustive]
-pub enum Error {
+pub enum OrcError {
     /// General ORC error.
     General(String),
     /// Error caused when an ORC file doesn't get parsed correctly.
     ParseError(String),
+    /// Error caused by an invalid or unsupported [`DataType`](crate::data_type::DataType).
+    DataTypeError(String),
     /// An error originating from a consumer or dependency
     External(String, Arc<dyn std::error::Error + Send + Sync>),
 }
 
-impl std::error::Error for Error {}
\ No newline at end of file
+impl fmt::Display for OrcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrcError::General(message) => write!(f, "General error: {message}"),
+            OrcError::ParseError(message) => write!(f, "Parse error: {message}"),
+            OrcError::DataTypeError(message) => write!(f, "Data type error: {message}"),
+            OrcError::External(message, source) => {
+                write!(f, "External error: {message}: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OrcError {}
@@ -0,0 +1,355 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A recursive-descent parser for the ORC canonical type string grammar,
+//! e.g. `struct<foo:int,bar:varchar(20),baz:array<map<string,double>>>`.
+//!
+//! See <https://orc.apache.org/specification/ORCv1/> for the grammar this
+//! mirrors.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use crate::data_type::{
+    create_char_type, create_decimal_type, create_list_type, create_map_type,
+    create_primitive_type, create_struct_type, create_union_type, DataType, Field, ThinType,
+    TypeKind,
+};
+use crate::error::{OrcError, OrcResult};
+
+impl FromStr for Box<DataType> {
+    type Err = OrcError;
+
+    fn from_str(s: &str) -> OrcResult<Self> {
+        let mut parser = Parser::new(s);
+        let result = parser.parse_type()?;
+        parser.skip_whitespace();
+        if parser.pos != parser.chars.len() {
+            let trailing: String = parser.chars[parser.pos..].iter().collect();
+            return Err(OrcError::ParseError(format!(
+                "Trailing garbage after type string: {trailing:?}"
+            )));
+        }
+        Ok(result)
+    }
+}
+
+struct Parser<'a> {
+    chars: Vec<char>,
+    pos: usize,
+    source: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Parser {
+            chars: source.chars().collect(),
+            pos: 0,
+            source,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.chars.len() && self.chars[self.pos].is_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.get(self.pos).copied()
+    }
+
+    fn expect_char(&mut self, expected: char) -> OrcResult<()> {
+        self.skip_whitespace();
+        match self.chars.get(self.pos) {
+            Some(&c) if c == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(&c) => Err(OrcError::ParseError(format!(
+                "Expected '{expected}' but found '{c}' at position {}",
+                self.pos
+            ))),
+            None => Err(OrcError::ParseError(format!(
+                "Expected '{expected}' but reached end of input"
+            ))),
+        }
+    }
+
+    /// Lexes a bare identifier: a run of alphanumeric characters or underscores.
+    fn lex_identifier(&mut self) -> OrcResult<String> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self
+            .chars
+            .get(self.pos)
+            .is_some_and(|c| c.is_alphanumeric() || *c == '_')
+        {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Err(OrcError::ParseError(format!(
+                "Expected an identifier at position {start} in {:?}",
+                self.source
+            )));
+        }
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    /// Lexes a non-negative integer literal.
+    fn lex_number(&mut self) -> OrcResult<u64> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self.chars.get(self.pos).is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Err(OrcError::ParseError(format!(
+                "Expected a number at position {start} in {:?}",
+                self.source
+            )));
+        }
+        self.chars[start..self.pos]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .map_err(|_| OrcError::ParseError(format!("Invalid number at position {start}")))
+    }
+
+    /// Parses zero or more parenthesized numeric args, e.g. `(10,2)`.
+    fn parse_args(&mut self) -> OrcResult<Vec<u64>> {
+        if self.peek() != Some('(') {
+            return Ok(Vec::new());
+        }
+        self.expect_char('(')?;
+        let mut args = vec![self.lex_number()?];
+        while self.peek() == Some(',') {
+            self.expect_char(',')?;
+            args.push(self.lex_number()?);
+        }
+        self.expect_char(')')?;
+        Ok(args)
+    }
+
+    fn parse_type(&mut self) -> OrcResult<Box<DataType>> {
+        let keyword = self.lex_identifier()?;
+        match keyword.to_ascii_lowercase().as_str() {
+            "boolean" => create_primitive_type(&TypeKind::Boolean),
+            "tinyint" => create_primitive_type(&TypeKind::Byte),
+            "smallint" => create_primitive_type(&TypeKind::Short),
+            "int" => create_primitive_type(&TypeKind::Int),
+            "bigint" => create_primitive_type(&TypeKind::Long),
+            "float" => create_primitive_type(&TypeKind::Float),
+            "double" => create_primitive_type(&TypeKind::Double),
+            "string" => create_primitive_type(&TypeKind::String),
+            "binary" => create_primitive_type(&TypeKind::Binary),
+            "date" => create_primitive_type(&TypeKind::Date),
+            "timestamp" => {
+                if self.try_consume_local_time_zone_suffix()? {
+                    create_primitive_type(&TypeKind::TimestampInstant)
+                } else {
+                    create_primitive_type(&TypeKind::Timestamp)
+                }
+            }
+            "char" | "varchar" => {
+                let kind = if keyword.eq_ignore_ascii_case("char") {
+                    TypeKind::Char
+                } else {
+                    TypeKind::Varchar
+                };
+                let args = self.parse_args()?;
+                match args.as_slice() {
+                    [max_length] => create_char_type(&kind, *max_length),
+                    _ => Err(OrcError::ParseError(format!(
+                        "{keyword} requires exactly one argument, e.g. {keyword}(20)"
+                    ))),
+                }
+            }
+            "decimal" => {
+                let args = self.parse_args()?;
+                match args.as_slice() {
+                    [precision, scale] => {
+                        create_decimal_type(&TypeKind::Decimal, *precision, *scale)
+                    }
+                    _ => Err(OrcError::ParseError(
+                        "decimal requires exactly two arguments, e.g. decimal(10,2)".to_string(),
+                    )),
+                }
+            }
+            "array" => {
+                self.expect_char('<')?;
+                let element_type = self.parse_type()?;
+                self.expect_char('>')?;
+                create_list_type(&element_type)
+            }
+            "map" => {
+                self.expect_char('<')?;
+                let key_type = self.parse_type()?;
+                self.expect_char(',')?;
+                let value_type = self.parse_type()?;
+                self.expect_char('>')?;
+                create_map_type(&key_type, &value_type)
+            }
+            "struct" => {
+                self.expect_char('<')?;
+                let mut datatype = create_struct_type()?;
+                let mut seen_names = HashSet::new();
+                if self.peek() != Some('>') {
+                    loop {
+                        let name = self.lex_identifier()?;
+                        if !seen_names.insert(name.clone()) {
+                            return Err(OrcError::ParseError(format!(
+                                "Duplicate struct field name: {name}"
+                            )));
+                        }
+                        self.expect_char(':')?;
+                        let field_type = self.parse_type()?;
+                        if let ThinType::Struct(fields) = &mut datatype.thin_type {
+                            fields.push(Box::new(Field {
+                                name,
+                                datatype: field_type,
+                            }));
+                            datatype.subtype_count = fields.len();
+                        }
+                        if self.peek() == Some(',') {
+                            self.expect_char(',')?;
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                self.expect_char('>')?;
+                Ok(datatype)
+            }
+            "uniontype" => {
+                self.expect_char('<')?;
+                let mut datatype = create_union_type()?;
+                if self.peek() != Some('>') {
+                    loop {
+                        let variant_type = self.parse_type()?;
+                        if let ThinType::Union(variants) = &mut datatype.thin_type {
+                            variants.push(variant_type);
+                            datatype.subtype_count = variants.len();
+                        }
+                        if self.peek() == Some(',') {
+                            self.expect_char(',')?;
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                self.expect_char('>')?;
+                Ok(datatype)
+            }
+            other => Err(OrcError::ParseError(format!(
+                "Unknown ORC type keyword: {other:?}"
+            ))),
+        }
+    }
+
+    /// Consumes the optional `with local time zone` suffix to `timestamp`.
+    fn try_consume_local_time_zone_suffix(&mut self) -> OrcResult<bool> {
+        let checkpoint = self.pos;
+        self.skip_whitespace();
+        for word in ["with", "local", "time", "zone"] {
+            self.skip_whitespace();
+            let start = self.pos;
+            while self
+                .chars
+                .get(self.pos)
+                .is_some_and(|c| c.is_alphanumeric())
+            {
+                self.pos += 1;
+            }
+            let got: String = self.chars[start..self.pos].iter().collect();
+            if !got.eq_ignore_ascii_case(word) {
+                self.pos = checkpoint;
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_primitive() {
+        let datatype = Box::<DataType>::from_str("int").unwrap();
+        assert_eq!(datatype.thin_type, ThinType::Int);
+    }
+
+    #[test]
+    fn test_parse_char_and_decimal() {
+        let datatype = Box::<DataType>::from_str("varchar(20)").unwrap();
+        assert_eq!(datatype.thin_type, ThinType::Varchar(20));
+
+        let datatype = Box::<DataType>::from_str("decimal(10,2)").unwrap();
+        assert_eq!(datatype.thin_type, ThinType::Decimal(10, 2));
+    }
+
+    #[test]
+    fn test_parse_nested_struct() {
+        let s = "struct<foo:int,bar:varchar(20),baz:array<map<string,double>>>";
+        let datatype = Box::<DataType>::from_str(s).unwrap();
+        assert_eq!(datatype.to_string(), s);
+    }
+
+    #[test]
+    fn test_parse_rejects_trailing_garbage() {
+        let result = Box::<DataType>::from_str("int garbage");
+        assert!(matches!(result, Err(OrcError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_parse_rejects_non_ascii_trailing_garbage_without_panicking() {
+        let result = Box::<DataType>::from_str("struct<名名名:int>名");
+        assert!(matches!(result, Err(OrcError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_parse_rejects_unbalanced_brackets() {
+        let result = Box::<DataType>::from_str("array<int");
+        assert!(matches!(result, Err(OrcError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_parse_rejects_duplicate_struct_field_names() {
+        let result = Box::<DataType>::from_str("struct<a:int,a:double>");
+        assert!(matches!(result, Err(OrcError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_display_round_trips_through_parse() {
+        let datatype = create_union_type().unwrap();
+        let s = datatype.to_string();
+        let reparsed = Box::<DataType>::from_str(&s).unwrap();
+        assert_eq!(reparsed.thin_type, datatype.thin_type);
+    }
+
+    #[test]
+    fn test_parse_empty_struct() {
+        let datatype = create_struct_type().unwrap();
+        let s = datatype.to_string();
+        assert_eq!(s, "struct<>");
+        let reparsed = Box::<DataType>::from_str(&s).unwrap();
+        assert_eq!(reparsed.thin_type, datatype.thin_type);
+    }
+}